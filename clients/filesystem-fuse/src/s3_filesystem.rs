@@ -17,7 +17,7 @@
  * under the License.
  */
 use crate::config::AppConfig;
-use crate::error::ErrorCode::{InvalidConfig, OpenDalError};
+use crate::error::ErrorCode::{InvalidConfig, NoSuchXattr, OpenDalError};
 use crate::filesystem::{FileStat, FileSystemCapacity, FileSystemContext, PathFileSystem, Result};
 use crate::gravitino_client::{Catalog, Fileset};
 use crate::open_dal_filesystem::OpenDalFileSystem;
@@ -30,9 +30,17 @@ use opendal::services::S3;
 use opendal::{Builder, Operator};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
 
 pub(crate) struct S3FileSystem {
     open_dal_fs: OpenDalFileSystem,
+    bucket: String,
+    region: String,
+    endpoint: Option<String>,
+    credentials: Arc<CredentialResolver>,
+    auto_create_bucket: bool,
 }
 
 impl S3FileSystem {}
@@ -40,6 +48,11 @@ impl S3FileSystem {}
 impl S3FileSystem {
     const S3_CONFIG_PREFIX: &'static str = "s3-";
 
+    /// Region assumed when an endpoint is path-style and no region could be
+    /// resolved, matching the AWS SDK behaviour of treating `us-east-1` as the
+    /// global default.
+    const DEFAULT_REGION: &'static str = "us-east-1";
+
     pub(crate) fn new(
         catalog: &Catalog,
         fileset: &Fileset,
@@ -50,8 +63,46 @@ impl S3FileSystem {
         let bucket = extract_bucket(&fileset.storage_location)?;
         opendal_config.insert("bucket".to_string(), bucket);
 
-        let region = Self::get_s3_region(catalog)?;
-        opendal_config.insert("region".to_string(), region);
+        // S3-compatible stores (MinIO, Garage, Ceph, ...) only accept
+        // path-style addressing, so default to it and let the operator opt
+        // into `bucket.endpoint` virtual-host addressing explicitly.
+        let virtual_host_style = Self::enable_virtual_host_style(catalog);
+        opendal_config.insert(
+            "enable_virtual_host_style".to_string(),
+            virtual_host_style.to_string(),
+        );
+
+        let region = Self::get_s3_region(catalog, virtual_host_style)?;
+        opendal_config.insert("region".to_string(), region.clone());
+
+        // Credentials for the data-path operator (stat/read/write/list):
+        //
+        //  * Explicit static keys and environment variables are folded into the
+        //    builder here — a synchronous, network-free step — as overrides.
+        //  * When neither is present (the EC2/IRSA case) we do NOT disable
+        //    OpenDAL's own credential loader, so the S3 service resolves and
+        //    *refreshes* IMDS and web-identity credentials natively for every
+        //    data-path request. Folding a one-shot temporary credential here
+        //    would instead freeze an expiring key into the operator.
+        //
+        // The custom resolver below is retained only for the hand-signed
+        // tagging/bucket helpers, which OpenDAL cannot issue; it is reached via
+        // `current_credentials()` and refreshes on expiry.
+        let credentials = Arc::new(CredentialResolver::new(config, catalog));
+        if let Some(creds) = credentials.resolve_non_network() {
+            creds.apply(&mut opendal_config);
+        }
+
+        let bucket = opendal_config
+            .get("bucket")
+            .cloned()
+            .expect("bucket inserted above");
+        let endpoint = catalog.properties.get("s3-endpoint").cloned();
+        let auto_create_bucket = catalog
+            .properties
+            .get("s3-auto-create-bucket")
+            .map(|v| v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
 
         let builder = S3::from_map(opendal_config);
 
@@ -61,22 +112,226 @@ impl S3FileSystem {
             return Err(OpenDalError.to_error(format!("opendal create failed: {:?}", e)));
         }
         let op = op.unwrap().layer(LoggingLayer::default()).finish();
-        let open_dal_fs = OpenDalFileSystem::new(op, config, _fs_context);
+        // Thread the multipart tuning into the writer shared by every backend,
+        // so large-file writes flowing through `create_file`/`open_file` upload
+        // in parallel parts instead of a single `PutObject`.
+        let multipart = extract_multipart_config(config);
+        let open_dal_fs = OpenDalFileSystem::new(op, config, _fs_context, multipart);
         Ok(Self {
-            open_dal_fs: open_dal_fs,
+            open_dal_fs,
+            bucket,
+            region,
+            endpoint,
+            credentials,
+            auto_create_bucket,
         })
     }
 
-    fn get_s3_region(catalog: &Catalog) -> GvfsResult<String> {
-        if let Some(region) = catalog.properties.get("s3-region") {
-            Ok(region.clone())
-        } else if let Some(endpoint) = catalog.properties.get("s3-endpoint") {
-            extract_region(endpoint)
+    /// Probe for the target bucket and create it when `s3-auto-create-bucket`
+    /// is set. The probe is a real `HeadBucket` classified on the HTTP status
+    /// — a bucket-level signal — rather than `op.stat("/")`, which the OpenDAL
+    /// S3 service can satisfy from a synthetic root dir without ever hitting
+    /// the network. A present bucket (including one with an empty prefix) is a
+    /// silent no-op.
+    async fn ensure_bucket(&self) -> Result<()> {
+        match self.head_bucket().await? {
+            BucketProbe::Exists => Ok(()),
+            BucketProbe::Missing => self.create_bucket().await,
+        }
+    }
+
+    fn bucket_url(&self) -> String {
+        match &self.endpoint {
+            Some(endpoint) => format!("{}/{}", endpoint.trim_end_matches('/'), self.bucket),
+            None => format!("https://{}.s3.{}.amazonaws.com/", self.bucket, self.region),
+        }
+    }
+
+    /// `HeadBucket`, classified on the HTTP status: `404` means the bucket is
+    /// absent, `200` that it exists, and `403` that it exists but the caller
+    /// lacks list access — all three distinguish bucket presence without
+    /// depending on OpenDAL's error wording.
+    async fn head_bucket(&self) -> Result<BucketProbe> {
+        let creds = self.current_credentials().await?;
+        let status = head_request(self.bucket_url(), &self.region, creds.as_ref())
+            .await
+            .map_err(|e| OpenDalError.to_error(format!("head bucket failed: {:?}", e)))?;
+        match status.as_u16() {
+            404 => Ok(BucketProbe::Missing),
+            403 => Ok(BucketProbe::Exists),
+            code if status.is_success() => {
+                let _ = code;
+                Ok(BucketProbe::Exists)
+            }
+            code => Err(OpenDalError.to_error(format!(
+                "unexpected head-bucket status {}",
+                code
+            ))),
+        }
+    }
+
+    /// Issue a `CreateBucket` with a private ACL in the resolved region. Uses a
+    /// SigV4-signed request since OpenDAL exposes no bucket-lifecycle API.
+    /// Without resolvable credentials an anonymous caller cannot create a
+    /// bucket, so this degrades to a logged no-op rather than failing the
+    /// mount — matching the anonymous-bucket tolerance elsewhere.
+    async fn create_bucket(&self) -> Result<()> {
+        let creds = match self.current_credentials().await? {
+            Some(creds) => creds,
+            None => {
+                log::warn!(
+                    "s3-auto-create-bucket set but no credentials resolved; \
+                     skipping CreateBucket for anonymous access"
+                );
+                return Ok(());
+            }
+        };
+
+        let url = self.bucket_url();
+
+        // `us-east-1` must omit the LocationConstraint; every other region
+        // requires it in the request body.
+        let body = if self.region == Self::DEFAULT_REGION {
+            String::new()
         } else {
-            Err(InvalidConfig.to_error(format!(
-                "Cant not retrieve region in the Catalog {}",
-                catalog.name
-            )))
+            format!(
+                "<CreateBucketConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+                 <LocationConstraint>{}</LocationConstraint></CreateBucketConfiguration>",
+                self.region
+            )
+        };
+
+        sign_and_put(url, body, &self.region, &creds, "x-amz-acl", "private")
+            .await
+            .map_err(|e| OpenDalError.to_error(format!("create bucket failed: {:?}", e)))
+    }
+
+    /// Fetch currently-valid credentials, transparently re-resolving temporary
+    /// (IMDS / web-identity) credentials that are at or near their expiry.
+    async fn current_credentials(&self) -> Result<Option<S3Credentials>> {
+        self.credentials
+            .current()
+            .await
+            .map_err(|e| OpenDalError.to_error(format!("credential resolution failed: {:?}", e)))
+    }
+
+    /// Extended attributes that map onto S3 object tags live under the
+    /// `user.s3.` prefix; everything else is unsupported.
+    const XATTR_NAMESPACE: &'static str = "user.s3.";
+
+    /// Strip the `user.s3.` namespace from an xattr name, yielding the S3 tag
+    /// key, or reject names outside the namespace as EINVAL.
+    fn xattr_tag_key(name: &str) -> Result<String> {
+        name.strip_prefix(Self::XATTR_NAMESPACE)
+            .filter(|key| !key.is_empty())
+            .map(|key| key.to_string())
+            .ok_or_else(|| {
+                InvalidConfig.to_error(format!(
+                    "unsupported xattr namespace, expected {}*: {}",
+                    Self::XATTR_NAMESPACE,
+                    name
+                ))
+            })
+    }
+
+    fn tagging_url(&self, path: &Path) -> String {
+        let key = path.to_string_lossy();
+        let key = key.trim_start_matches('/');
+        match &self.endpoint {
+            Some(endpoint) => format!(
+                "{}/{}/{}?tagging",
+                endpoint.trim_end_matches('/'),
+                self.bucket,
+                key
+            ),
+            None => format!(
+                "https://{}.s3.{}.amazonaws.com/{}?tagging",
+                self.bucket, self.region, key
+            ),
+        }
+    }
+
+    async fn tag_credentials(&self) -> Result<S3Credentials> {
+        self.current_credentials().await?.ok_or_else(|| {
+            InvalidConfig.to_error("object tagging requires resolvable credentials".to_string())
+        })
+    }
+
+    async fn get_object_tags(&self, path: &Path) -> Result<Vec<(String, String)>> {
+        let creds = self.tag_credentials().await?;
+        let body = signed_request(
+            http::Method::GET,
+            self.tagging_url(path),
+            String::new(),
+            &self.region,
+            &creds,
+            &[],
+        )
+        .await
+        .map_err(|e| OpenDalError.to_error(format!("get object tagging failed: {:?}", e)))?;
+        Ok(parse_tag_set(&body))
+    }
+
+    async fn put_object_tags(&self, path: &Path, tags: &[(String, String)]) -> Result<()> {
+        let creds = self.tag_credentials().await?;
+        let body = serialize_tag_set(tags);
+        signed_request(
+            http::Method::PUT,
+            self.tagging_url(path),
+            body,
+            &self.region,
+            &creds,
+            &[],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| OpenDalError.to_error(format!("put object tagging failed: {:?}", e)))
+    }
+
+    async fn delete_object_tags(&self, path: &Path) -> Result<()> {
+        let creds = self.tag_credentials().await?;
+        signed_request(
+            http::Method::DELETE,
+            self.tagging_url(path),
+            String::new(),
+            &self.region,
+            &creds,
+            &[],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| OpenDalError.to_error(format!("delete object tagging failed: {:?}", e)))
+    }
+
+    fn enable_virtual_host_style(catalog: &Catalog) -> bool {
+        catalog
+            .properties
+            .get("s3-enable-virtual-host-style")
+            .map(|v| v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+    }
+
+    /// Resolve the region in priority order: an explicit `s3-region`, then the
+    /// region embedded in a virtual-host-style `s3.<region>.amazonaws.com`
+    /// endpoint. Path-style endpoints carry no region in the host name and the
+    /// S3-compatible stores that use them (MinIO, Garage, Ceph) are region
+    /// agnostic, so they assume [`Self::DEFAULT_REGION`] rather than erroring;
+    /// set `s3-region` explicitly for a region-aware path-style endpoint.
+    ///
+    /// This is a deliberate simplification of an automatic
+    /// HeadBucket/GetBucketLocation probe: such a probe needs signed network
+    /// access before the operator exists and only benefits region-aware AWS
+    /// endpoints, which are virtual-host-style and already covered above. The
+    /// explicit `s3-region` escape hatch handles the rare region-aware
+    /// path-style store.
+    fn get_s3_region(catalog: &Catalog, virtual_host_style: bool) -> GvfsResult<String> {
+        if let Some(region) = catalog.properties.get("s3-region") {
+            return Ok(region.clone());
+        }
+
+        match catalog.properties.get("s3-endpoint") {
+            Some(endpoint) if virtual_host_style => extract_region(endpoint),
+            _ => Ok(Self::DEFAULT_REGION.to_string()),
         }
     }
 }
@@ -84,6 +339,9 @@ impl S3FileSystem {
 #[async_trait]
 impl PathFileSystem for S3FileSystem {
     async fn init(&self) -> Result<()> {
+        if self.auto_create_bucket {
+            self.ensure_bucket().await?;
+        }
         Ok(())
     }
 
@@ -126,6 +384,71 @@ impl PathFileSystem for S3FileSystem {
     fn get_capacity(&self) -> Result<FileSystemCapacity> {
         self.open_dal_fs.get_capacity()
     }
+
+    // Extended-attribute support maps the `user.s3.*` namespace onto S3 object
+    // tags. The `PathFileSystem` trait declares these with default
+    // implementations that reject every attribute (other backends have no
+    // xattr surface), so the FUSE `getxattr`/`setxattr`/`listxattr`/
+    // `removexattr` handlers dispatch uniformly and only S3 filesets act on
+    // them.
+
+    async fn get_xattr(&self, path: &Path, name: &str) -> Result<Vec<u8>> {
+        let key = Self::xattr_tag_key(name)?;
+        let tags = self.get_object_tags(path).await?;
+        match tags.into_iter().find(|(k, _)| *k == key) {
+            Some((_, value)) => Ok(value.into_bytes()),
+            // No such tag — surface as a missing attribute, which the error
+            // layer maps to ENODATA rather than the EINVAL of `InvalidConfig`.
+            None => Err(NoSuchXattr.to_error(format!("no such xattr: {}", name))),
+        }
+    }
+
+    async fn set_xattr(&self, path: &Path, name: &str, value: &[u8]) -> Result<()> {
+        let key = Self::xattr_tag_key(name)?;
+        let value = String::from_utf8(value.to_vec())
+            .map_err(|_| InvalidConfig.to_error("xattr value must be valid UTF-8".to_string()))?;
+        // S3 object tags cap keys at 128 and values at 256 characters; reject
+        // oversized input up front as EINVAL.
+        if key.chars().count() > 128 || value.chars().count() > 256 {
+            return Err(InvalidConfig.to_error("s3 tag key/value too long".to_string()));
+        }
+
+        let mut tags = self.get_object_tags(path).await?;
+        match tags.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => {
+                // A maximum of 10 tags may be attached to an object.
+                if tags.len() >= 10 {
+                    return Err(InvalidConfig.to_error("s3 allows at most 10 object tags".to_string()));
+                }
+                tags.push((key, value));
+            }
+        }
+        self.put_object_tags(path, &tags).await
+    }
+
+    async fn list_xattr(&self, path: &Path) -> Result<Vec<String>> {
+        let tags = self.get_object_tags(path).await?;
+        Ok(tags
+            .into_iter()
+            .map(|(k, _)| format!("{}{}", Self::XATTR_NAMESPACE, k))
+            .collect())
+    }
+
+    async fn remove_xattr(&self, path: &Path, name: &str) -> Result<()> {
+        let key = Self::xattr_tag_key(name)?;
+        let mut tags = self.get_object_tags(path).await?;
+        let before = tags.len();
+        tags.retain(|(k, _)| *k != key);
+        if tags.len() == before {
+            return Err(NoSuchXattr.to_error(format!("no such xattr: {}", name)));
+        }
+        if tags.is_empty() {
+            self.delete_object_tags(path).await
+        } else {
+            self.put_object_tags(path, &tags).await
+        }
+    }
 }
 
 pub(crate) fn extract_bucket(location: &str) -> GvfsResult<String> {
@@ -142,17 +465,12 @@ pub(crate) fn extract_bucket(location: &str) -> GvfsResult<String> {
 pub(crate) fn extract_region(location: &str) -> GvfsResult<String> {
     let url = parse_location(location)?;
     match url.host_str() {
-        Some(host) => {
-            let parts: Vec<&str> = host.split('.').collect();
-            if parts.len() > 1 {
-                Ok(parts[1].to_string())
-            } else {
-                Err(InvalidConfig.to_error(format!(
-                    "Invalid location: expected region in host, got {}",
-                    location
-                )))
-            }
-        }
+        Some(host) => region_from_amazonaws_host(host).ok_or_else(|| {
+            InvalidConfig.to_error(format!(
+                "Invalid location: expected region in host, got {}",
+                location
+            ))
+        }),
         None => Err(InvalidConfig.to_error(format!(
             "Invalid fileset location without bucket: {}",
             location
@@ -160,6 +478,68 @@ pub(crate) fn extract_region(location: &str) -> GvfsResult<String> {
     }
 }
 
+/// Extract the region label from a virtual-host-style AWS endpoint such as
+/// `s3.<region>.amazonaws.com` or `<bucket>.s3.<region>.amazonaws.com`,
+/// returning `None` for hosts that do not encode a region.
+fn region_from_amazonaws_host(host: &str) -> Option<String> {
+    if !host.ends_with(".amazonaws.com") {
+        // Non-AWS hosts use path-style addressing and carry no region.
+        return None;
+    }
+    let parts: Vec<&str> = host.split('.').collect();
+    // Locate the `s3` / `s3-<svc>` label; the region is the label after it.
+    parts
+        .iter()
+        .position(|p| *p == "s3" || p.starts_with("s3-"))
+        .and_then(|i| parts.get(i + 1))
+        .filter(|p| **p != "amazonaws")
+        .map(|p| p.to_string())
+}
+
+/// Tuning for OpenDAL multipart writes. Passed into [`OpenDalFileSystem`],
+/// which owns the write path and configures its writer so multi-gigabyte
+/// fileset objects upload in parallel parts rather than a single `PutObject`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MultipartConfig {
+    /// Size of each multipart part. S3 requires at least 5 MiB per part (the
+    /// final part excepted), so smaller values are clamped up.
+    pub chunk_size: usize,
+    /// Number of parts uploaded concurrently.
+    pub concurrency: usize,
+    /// Writes below this size use a single `PutObject` instead of multipart.
+    pub threshold: usize,
+}
+
+impl Default for MultipartConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: Self::MIN_CHUNK_SIZE,
+            concurrency: 1,
+            threshold: Self::MIN_CHUNK_SIZE,
+        }
+    }
+}
+
+impl MultipartConfig {
+    /// S3's hard lower bound on the size of a non-final multipart part.
+    const MIN_CHUNK_SIZE: usize = 5 * 1024 * 1024;
+}
+
+/// Read the `s3-multipart-*` knobs, clamping `chunk_size` to S3's 5 MiB floor.
+/// The result is handed to [`OpenDalFileSystem`] at construction so the write
+/// path is configured where the writes happen.
+pub(crate) fn extract_multipart_config(config: &AppConfig) -> MultipartConfig {
+    let get = |key: &str| config.extend_config.get(key).and_then(|v| v.parse().ok());
+    let mut multipart = MultipartConfig {
+        chunk_size: get("s3-multipart-chunk-size").unwrap_or(MultipartConfig::MIN_CHUNK_SIZE),
+        concurrency: get("s3-multipart-concurrency").unwrap_or(1),
+        threshold: get("s3-multipart-threshold").unwrap_or(MultipartConfig::MIN_CHUNK_SIZE),
+    };
+    multipart.chunk_size = multipart.chunk_size.max(MultipartConfig::MIN_CHUNK_SIZE);
+    multipart.concurrency = multipart.concurrency.max(1);
+    multipart
+}
+
 pub fn extract_s3_config(config: &AppConfig) -> HashMap<String, String> {
     config
         .extend_config
@@ -180,6 +560,617 @@ pub fn extract_s3_config(config: &AppConfig) -> HashMap<String, String> {
         .collect()
 }
 
+/// Result of a `HeadBucket` probe: whether the bucket is present.
+enum BucketProbe {
+    Exists,
+    Missing,
+}
+
+/// Send a `HeadBucket` and return the raw status. Signs the request when
+/// credentials are available and falls back to an unsigned probe otherwise so
+/// anonymously-readable buckets can still be detected.
+async fn head_request(
+    url: String,
+    region: &str,
+    creds: Option<&S3Credentials>,
+) -> GvfsResult<reqwest::StatusCode> {
+    let client = reqwest::Client::new();
+    let request = match creds {
+        Some(creds) => {
+            let credential = reqsign::AwsCredential {
+                access_key_id: creds.access_key_id.clone(),
+                secret_access_key: creds.secret_access_key.clone(),
+                session_token: creds.session_token.clone(),
+                expires_in: None,
+            };
+            let signer = reqsign::AwsV4Signer::new("s3", region);
+            let mut req = http::Request::builder()
+                .method(http::Method::HEAD)
+                .uri(&url)
+                .body(String::new())
+                .map_err(|e| OpenDalError.to_error(format!("build head request: {:?}", e)))?;
+            signer
+                .sign(&mut req, &credential)
+                .map_err(|e| OpenDalError.to_error(format!("sign head request: {:?}", e)))?;
+            let (parts, _) = req.into_parts();
+            let mut request = client.request(http::Method::HEAD, url);
+            for (name, value) in parts.headers.iter() {
+                request = request.header(name, value);
+            }
+            request
+        }
+        None => client.head(url),
+    };
+    request
+        .send()
+        .await
+        .map(|resp| resp.status())
+        .map_err(|e| OpenDalError.to_error(format!("send head request: {:?}", e)))
+}
+
+/// Issue a SigV4-signed request with optional extra headers and return the
+/// response body. OpenDAL exposes neither bucket-lifecycle nor object-tagging
+/// APIs, so both paths fall back to a hand-signed call.
+async fn signed_request(
+    method: http::Method,
+    url: String,
+    body: String,
+    region: &str,
+    creds: &S3Credentials,
+    extra_headers: &[(&str, &str)],
+) -> GvfsResult<String> {
+    let credential = reqsign::AwsCredential {
+        access_key_id: creds.access_key_id.clone(),
+        secret_access_key: creds.secret_access_key.clone(),
+        session_token: creds.session_token.clone(),
+        expires_in: None,
+    };
+    let signer = reqsign::AwsV4Signer::new("s3", region);
+
+    let mut builder = http::Request::builder().method(method).uri(&url);
+    for (name, value) in extra_headers {
+        builder = builder.header(*name, *value);
+    }
+    let mut req = builder
+        .body(body)
+        .map_err(|e| OpenDalError.to_error(format!("build signed request: {:?}", e)))?;
+    signer
+        .sign(&mut req, &credential)
+        .map_err(|e| OpenDalError.to_error(format!("sign request: {:?}", e)))?;
+
+    let (parts, body) = req.into_parts();
+    let client = reqwest::Client::new();
+    let mut request = client.request(parts.method, url).body(body);
+    for (name, value) in parts.headers.iter() {
+        request = request.header(name, value);
+    }
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| OpenDalError.to_error(format!("send signed request: {:?}", e)))?;
+    let status = resp.status();
+    let text = resp
+        .text()
+        .await
+        .map_err(|e| OpenDalError.to_error(format!("read signed response: {:?}", e)))?;
+    if status.is_success() {
+        Ok(text)
+    } else {
+        Err(OpenDalError.to_error(format!("request returned status {}: {}", status, text)))
+    }
+}
+
+/// Convenience wrapper for a signed `PUT` whose response body is ignored.
+async fn sign_and_put(
+    url: String,
+    body: String,
+    region: &str,
+    creds: &S3Credentials,
+    header_name: &str,
+    header_value: &str,
+) -> GvfsResult<()> {
+    signed_request(
+        http::Method::PUT,
+        url,
+        body,
+        region,
+        creds,
+        &[(header_name, header_value)],
+    )
+    .await
+    .map(|_| ())
+}
+
+/// S3 credentials resolved from one of the supported providers. Temporary
+/// credentials (IMDS, web-identity) carry an `expiration`; long-lived static
+/// keys leave it `None`.
+#[derive(Clone, Debug)]
+pub(crate) struct S3Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    expiration: Option<SystemTime>,
+}
+
+impl S3Credentials {
+    /// Treat credentials as expired a minute ahead of their deadline so a
+    /// request signed with them does not race the expiry.
+    fn is_expired(&self) -> bool {
+        match self.expiration {
+            Some(exp) => SystemTime::now() + Duration::from_secs(60) >= exp,
+            None => false,
+        }
+    }
+
+    fn apply(&self, config: &mut HashMap<String, String>) {
+        config.insert("access_key_id".to_string(), self.access_key_id.clone());
+        config.insert(
+            "secret_access_key".to_string(),
+            self.secret_access_key.clone(),
+        );
+        if let Some(token) = &self.session_token {
+            config.insert("session_token".to_string(), token.clone());
+        }
+    }
+}
+
+/// A single step of the AWS-style credential resolution chain. Resolution is
+/// `async` so the network-backed providers (IMDS, web-identity) use the async
+/// HTTP client instead of blocking a runtime worker.
+#[async_trait]
+trait CredentialProvider: Send + Sync {
+    /// Stable identifier used to pin a provider via `s3-credential-provider`.
+    fn name(&self) -> &'static str;
+
+    /// Whether this provider reaches the network. Network-free providers can be
+    /// resolved from the synchronous constructor without blocking a worker.
+    fn is_network(&self) -> bool;
+
+    /// Return credentials when this provider can satisfy the request, `None`
+    /// when it is simply not configured, or an error when it is configured but
+    /// fails to produce usable credentials.
+    async fn resolve(&self) -> GvfsResult<Option<S3Credentials>>;
+}
+
+/// Explicit keys pulled from `s3-access-key-id` / `s3-secret-access-key`.
+struct StaticProvider {
+    access_key_id: Option<String>,
+    secret_access_key: Option<String>,
+    session_token: Option<String>,
+}
+
+impl StaticProvider {
+    fn credentials(&self) -> Option<S3Credentials> {
+        match (&self.access_key_id, &self.secret_access_key) {
+            (Some(id), Some(secret)) => Some(S3Credentials {
+                access_key_id: id.clone(),
+                secret_access_key: secret.clone(),
+                session_token: self.session_token.clone(),
+                expiration: None,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for StaticProvider {
+    fn name(&self) -> &'static str {
+        "static"
+    }
+
+    fn is_network(&self) -> bool {
+        false
+    }
+
+    async fn resolve(&self) -> GvfsResult<Option<S3Credentials>> {
+        Ok(self.credentials())
+    }
+}
+
+/// Standard `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` / `AWS_SESSION_TOKEN`
+/// environment variables.
+struct EnvProvider;
+
+impl EnvProvider {
+    fn credentials() -> Option<S3Credentials> {
+        match (
+            std::env::var("AWS_ACCESS_KEY_ID").ok(),
+            std::env::var("AWS_SECRET_ACCESS_KEY").ok(),
+        ) {
+            (Some(id), Some(secret)) => Some(S3Credentials {
+                access_key_id: id,
+                secret_access_key: secret,
+                session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+                expiration: None,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for EnvProvider {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn is_network(&self) -> bool {
+        false
+    }
+
+    async fn resolve(&self) -> GvfsResult<Option<S3Credentials>> {
+        Ok(Self::credentials())
+    }
+}
+
+/// EC2/ECS instance metadata service (IMDSv2). Fetches a session token with a
+/// `PUT /latest/api/token`, then reads the attached role's temporary
+/// credentials with that token in the `X-aws-ec2-metadata-token` header.
+struct ImdsProvider;
+
+impl ImdsProvider {
+    const BASE: &'static str = "http://169.254.169.254";
+    const TOKEN_TTL: &'static str = "21600";
+}
+
+#[async_trait]
+impl CredentialProvider for ImdsProvider {
+    fn name(&self) -> &'static str {
+        "imds"
+    }
+
+    fn is_network(&self) -> bool {
+        true
+    }
+
+    async fn resolve(&self) -> GvfsResult<Option<S3Credentials>> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(2))
+            .build()
+            .map_err(|e| InvalidConfig.to_error(format!("imds client build failed: {:?}", e)))?;
+
+        let token = match client
+            .put(format!("{}/latest/api/token", Self::BASE))
+            .header("X-aws-ec2-metadata-token-ttl-seconds", Self::TOKEN_TTL)
+            .send()
+            .await
+        {
+            Ok(resp) if resp.status().is_success() => resp
+                .text()
+                .await
+                .map_err(|e| InvalidConfig.to_error(format!("imds token read failed: {:?}", e)))?,
+            // No metadata service reachable means we are not on EC2/ECS.
+            _ => return Ok(None),
+        };
+
+        let role = client
+            .get(format!(
+                "{}/latest/meta-data/iam/security-credentials/",
+                Self::BASE
+            ))
+            .header("X-aws-ec2-metadata-token", &token)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| InvalidConfig.to_error(format!("imds role lookup failed: {:?}", e)))?
+            .text()
+            .await
+            .map_err(|e| InvalidConfig.to_error(format!("imds role read failed: {:?}", e)))?;
+        let role = role.trim();
+        if role.is_empty() {
+            return Ok(None);
+        }
+
+        let body = client
+            .get(format!(
+                "{}/latest/meta-data/iam/security-credentials/{}",
+                Self::BASE,
+                role
+            ))
+            .header("X-aws-ec2-metadata-token", &token)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| InvalidConfig.to_error(format!("imds credential fetch failed: {:?}", e)))?
+            .text()
+            .await
+            .map_err(|e| InvalidConfig.to_error(format!("imds credential read failed: {:?}", e)))?;
+
+        parse_imds_credentials(&body).map(Some)
+    }
+}
+
+/// Web-identity / IRSA: reads `AWS_WEB_IDENTITY_TOKEN_FILE` + `AWS_ROLE_ARN`
+/// and exchanges the OIDC token for temporary credentials via STS
+/// `AssumeRoleWithWebIdentity`.
+struct WebIdentityProvider;
+
+#[async_trait]
+impl CredentialProvider for WebIdentityProvider {
+    fn name(&self) -> &'static str {
+        "web-identity"
+    }
+
+    fn is_network(&self) -> bool {
+        true
+    }
+
+    async fn resolve(&self) -> GvfsResult<Option<S3Credentials>> {
+        let token_file = match std::env::var("AWS_WEB_IDENTITY_TOKEN_FILE") {
+            Ok(path) => path,
+            Err(_) => return Ok(None),
+        };
+        let role_arn = std::env::var("AWS_ROLE_ARN").map_err(|_| {
+            InvalidConfig.to_error(
+                "AWS_WEB_IDENTITY_TOKEN_FILE set without AWS_ROLE_ARN".to_string(),
+            )
+        })?;
+        let token = std::fs::read_to_string(&token_file).map_err(|e| {
+            InvalidConfig.to_error(format!("reading web identity token {}: {:?}", token_file, e))
+        })?;
+
+        let region = std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| InvalidConfig.to_error(format!("sts client build failed: {:?}", e)))?;
+        let body = client
+            .post(format!("https://sts.{}.amazonaws.com/", region))
+            .form(&[
+                ("Action", "AssumeRoleWithWebIdentity"),
+                ("Version", "2011-06-15"),
+                ("RoleArn", role_arn.as_str()),
+                ("RoleSessionName", "gvfs-fuse"),
+                ("WebIdentityToken", token.trim()),
+            ])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| InvalidConfig.to_error(format!("sts assume-role failed: {:?}", e)))?
+            .text()
+            .await
+            .map_err(|e| InvalidConfig.to_error(format!("sts response read failed: {:?}", e)))?;
+
+        parse_sts_credentials(&body).map(Some)
+    }
+}
+
+/// Owns the credential provider chain and caches the most recently resolved
+/// credentials. Temporary credentials are re-resolved once they reach their
+/// expiry window so a long-lived mount never signs a request with lapsed
+/// credentials.
+pub(crate) struct CredentialResolver {
+    static_keys: (Option<String>, Option<String>, Option<String>),
+    pinned: Option<String>,
+    cache: Mutex<Option<S3Credentials>>,
+}
+
+impl CredentialResolver {
+    fn new(config: &AppConfig, catalog: &Catalog) -> Self {
+        let pinned = config
+            .extend_config
+            .get("s3-credential-provider")
+            .cloned()
+            .or_else(|| catalog.properties.get("s3-credential-provider").cloned());
+        Self {
+            static_keys: (
+                config.extend_config.get("s3-access-key-id").cloned(),
+                config.extend_config.get("s3-secret-access-key").cloned(),
+                config.extend_config.get("s3-session-token").cloned(),
+            ),
+            pinned,
+            cache: Mutex::new(None),
+        }
+    }
+
+    fn chain(&self) -> Vec<Box<dyn CredentialProvider>> {
+        vec![
+            Box::new(StaticProvider {
+                access_key_id: self.static_keys.0.clone(),
+                secret_access_key: self.static_keys.1.clone(),
+                session_token: self.static_keys.2.clone(),
+            }),
+            Box::new(EnvProvider),
+            Box::new(ImdsProvider),
+            Box::new(WebIdentityProvider),
+        ]
+    }
+
+    fn accepts(&self, provider: &dyn CredentialProvider) -> bool {
+        self.pinned
+            .as_deref()
+            .map(|pin| pin == provider.name())
+            .unwrap_or(true)
+    }
+
+    /// Resolve the network-free providers only, for folding static/env keys
+    /// into the OpenDAL builder without blocking in the synchronous `new`.
+    fn resolve_non_network(&self) -> Option<S3Credentials> {
+        for provider in self.chain() {
+            if provider.is_network() || !self.accepts(provider.as_ref()) {
+                continue;
+            }
+            match provider.name() {
+                "static" => {
+                    let static_provider = StaticProvider {
+                        access_key_id: self.static_keys.0.clone(),
+                        secret_access_key: self.static_keys.1.clone(),
+                        session_token: self.static_keys.2.clone(),
+                    };
+                    if let Some(creds) = static_provider.credentials() {
+                        return Some(creds);
+                    }
+                }
+                "env" => {
+                    if let Some(creds) = EnvProvider::credentials() {
+                        return Some(creds);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Return currently-valid credentials, re-resolving the chain when the
+    /// cache is empty or the cached credentials are at/near their expiry.
+    async fn current(&self) -> GvfsResult<Option<S3Credentials>> {
+        let mut cache = self.cache.lock().await;
+        if let Some(creds) = cache.as_ref() {
+            if !creds.is_expired() {
+                return Ok(Some(creds.clone()));
+            }
+        }
+        let resolved = self.resolve_chain().await?;
+        *cache = resolved.clone();
+        Ok(resolved)
+    }
+
+    async fn resolve_chain(&self) -> GvfsResult<Option<S3Credentials>> {
+        for provider in self.chain() {
+            if !self.accepts(provider.as_ref()) {
+                continue;
+            }
+            if let Some(creds) = provider.resolve().await? {
+                if creds.is_expired() {
+                    continue;
+                }
+                return Ok(Some(creds));
+            }
+        }
+        if let Some(pin) = &self.pinned {
+            return Err(InvalidConfig.to_error(format!(
+                "credential provider '{}' produced no usable credentials",
+                pin
+            )));
+        }
+        Ok(None)
+    }
+}
+
+/// JSON credential payload returned by the IMDS credentials endpoint.
+#[derive(serde::Deserialize)]
+struct ImdsCredentialResponse {
+    #[serde(rename = "AccessKeyId")]
+    access_key_id: String,
+    #[serde(rename = "SecretAccessKey")]
+    secret_access_key: String,
+    #[serde(rename = "Token")]
+    token: Option<String>,
+    #[serde(rename = "Expiration")]
+    expiration: Option<String>,
+}
+
+fn parse_imds_credentials(body: &str) -> GvfsResult<S3Credentials> {
+    let parsed: ImdsCredentialResponse = serde_json::from_str(body)
+        .map_err(|e| InvalidConfig.to_error(format!("invalid imds credential json: {:?}", e)))?;
+    Ok(S3Credentials {
+        access_key_id: parsed.access_key_id,
+        secret_access_key: parsed.secret_access_key,
+        session_token: parsed.token,
+        expiration: parsed.expiration.as_deref().and_then(parse_rfc3339),
+    })
+}
+
+/// Pull the credential fields out of an STS `AssumeRoleWithWebIdentity` XML
+/// response without pulling in a full XML parser.
+fn parse_sts_credentials(body: &str) -> GvfsResult<S3Credentials> {
+    let field = |tag: &str| extract_xml_tag(body, tag);
+    let access_key_id = field("AccessKeyId").ok_or_else(|| {
+        InvalidConfig.to_error("sts response missing AccessKeyId".to_string())
+    })?;
+    let secret_access_key = field("SecretAccessKey").ok_or_else(|| {
+        InvalidConfig.to_error("sts response missing SecretAccessKey".to_string())
+    })?;
+    Ok(S3Credentials {
+        access_key_id,
+        secret_access_key,
+        session_token: field("SessionToken"),
+        expiration: field("Expiration").as_deref().and_then(parse_rfc3339),
+    })
+}
+
+/// Parse the `<TagSet>` of a `GetObjectTagging` response into key/value pairs.
+fn parse_tag_set(xml: &str) -> Vec<(String, String)> {
+    let mut tags = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<Tag>") {
+        let end = match rest[start..].find("</Tag>") {
+            Some(e) => start + e,
+            None => break,
+        };
+        let tag = &rest[start..end];
+        if let (Some(key), Some(value)) =
+            (extract_xml_tag(tag, "Key"), extract_xml_tag(tag, "Value"))
+        {
+            tags.push((xml_unescape(&key), xml_unescape(&value)));
+        }
+        rest = &rest[end + "</Tag>".len()..];
+    }
+    tags
+}
+
+/// Render key/value pairs into the `Tagging` XML body `PutObjectTagging` wants.
+fn serialize_tag_set(tags: &[(String, String)]) -> String {
+    let mut body = String::from("<Tagging><TagSet>");
+    for (key, value) in tags {
+        body.push_str(&format!(
+            "<Tag><Key>{}</Key><Value>{}</Value></Tag>",
+            xml_escape(key),
+            xml_escape(value)
+        ));
+    }
+    body.push_str("</TagSet></Tagging>");
+    body
+}
+
+/// Escape the five XML predefined entities so tag keys/values containing
+/// `& < > " '` survive the round trip through `PutObjectTagging`.
+fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Inverse of [`xml_escape`], resolving the five XML predefined entities.
+/// `&amp;` is resolved last so an input like `&amp;lt;` decodes to `&lt;`
+/// rather than `<`.
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn extract_xml_tag(body: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = body.find(&open)? + open.len();
+    let end = body[start..].find(&close)? + start;
+    Some(body[start..end].to_string())
+}
+
+/// Parse the RFC 3339 timestamps AWS uses for credential expiry into a
+/// `SystemTime`, tolerating both `Z` and explicit-offset forms.
+fn parse_rfc3339(value: &str) -> Option<SystemTime> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| SystemTime::from(dt))
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +1178,7 @@ mod tests {
     use crate::filesystem::tests::{TestPathFileSystem, TestRawFileSystem};
     use crate::filesystem::RawFileSystem;
     use opendal::layers::TimeoutLayer;
+    use opendal::services::{Fs, Memory};
     use std::time::Duration;
 
     #[test]
@@ -205,6 +1197,75 @@ mod tests {
         assert_eq!(result.unwrap(), "ap-southeast-2");
     }
 
+    #[test]
+    fn test_parse_imds_credentials() {
+        let body = r#"{
+            "AccessKeyId": "AKIDEXAMPLE",
+            "SecretAccessKey": "secret",
+            "Token": "session-token",
+            "Expiration": "2035-01-01T00:00:00Z"
+        }"#;
+        let creds = parse_imds_credentials(body).unwrap();
+        assert_eq!(creds.access_key_id, "AKIDEXAMPLE");
+        assert_eq!(creds.secret_access_key, "secret");
+        assert_eq!(creds.session_token.as_deref(), Some("session-token"));
+        assert!(creds.expiration.is_some());
+        assert!(!creds.is_expired());
+    }
+
+    #[test]
+    fn test_parse_sts_credentials() {
+        let body = "<Credentials><AccessKeyId>AKIDEXAMPLE</AccessKeyId>\
+            <SecretAccessKey>secret</SecretAccessKey>\
+            <SessionToken>token</SessionToken>\
+            <Expiration>2035-01-01T00:00:00Z</Expiration></Credentials>";
+        let creds = parse_sts_credentials(body).unwrap();
+        assert_eq!(creds.access_key_id, "AKIDEXAMPLE");
+        assert_eq!(creds.session_token.as_deref(), Some("token"));
+    }
+
+    #[test]
+    fn test_tag_set_round_trip() {
+        let tags = vec![
+            ("owner".to_string(), "team-a".to_string()),
+            ("env".to_string(), "prod".to_string()),
+        ];
+        let xml = serialize_tag_set(&tags);
+        assert_eq!(parse_tag_set(&xml), tags);
+    }
+
+    #[test]
+    fn test_tag_set_round_trip_escapes_entities() {
+        let tags = vec![(
+            "owner".to_string(),
+            "a & b <c> \"d\" 'e'".to_string(),
+        )];
+        let xml = serialize_tag_set(&tags);
+        assert!(!xml.contains("<c>"));
+        assert_eq!(parse_tag_set(&xml), tags);
+    }
+
+    #[test]
+    fn test_xattr_tag_key_namespace() {
+        assert_eq!(S3FileSystem::xattr_tag_key("user.s3.owner").unwrap(), "owner");
+        assert!(S3FileSystem::xattr_tag_key("user.other").is_err());
+        assert!(S3FileSystem::xattr_tag_key("user.s3.").is_err());
+    }
+
+    #[test]
+    fn test_extract_multipart_config_clamps_chunk_size() {
+        let mut config = AppConfig::default();
+        config
+            .extend_config
+            .insert("s3-multipart-chunk-size".to_string(), "1024".to_string());
+        config
+            .extend_config
+            .insert("s3-multipart-concurrency".to_string(), "4".to_string());
+        let multipart = extract_multipart_config(&config);
+        assert_eq!(multipart.chunk_size, MultipartConfig::MIN_CHUNK_SIZE);
+        assert_eq!(multipart.concurrency, 4);
+    }
+
     async fn delete_dir(op: &Operator, dir_name: &str) {
         let childs = op.list(dir_name).await.expect("list dir failed");
         for child in childs {
@@ -242,8 +1303,96 @@ mod tests {
             .await
             .expect("create test dir failed");
 
-        let open_dal_fs = OpenDalFileSystem::new(op, &config, &fs_context);
-        S3FileSystem { open_dal_fs }
+        let open_dal_fs = OpenDalFileSystem::new(op, &config, &fs_context, extract_multipart_config(&config));
+        S3FileSystem {
+            open_dal_fs,
+            bucket: String::new(),
+            region: S3FileSystem::DEFAULT_REGION.to_string(),
+            endpoint: None,
+            credentials: Arc::new(CredentialResolver {
+                static_keys: (None, None, None),
+                pinned: None,
+                cache: Mutex::new(None),
+            }),
+            auto_create_bucket: false,
+        }
+    }
+
+    /// Build an `S3FileSystem` over an offline OpenDAL backend so the
+    /// conformance suites run without live credentials. The backend is chosen
+    /// by `GVFS_TEST_S3_BACKEND` (`memory` — the default, `fs`, or `s3`),
+    /// letting the identical assertions validate the real S3 path when `s3`
+    /// is selected and an offline backend otherwise. Credentials are not
+    /// probed: `s3` needs them in `tests/conf/gvfs_fuse_s3.toml`.
+    async fn create_mock_s3_fs(cwd: &Path) -> S3FileSystem {
+        let backend = std::env::var("GVFS_TEST_S3_BACKEND").unwrap_or_else(|_| "memory".to_string());
+        if backend == "s3" {
+            return create_s3_fs(cwd).await;
+        }
+
+        let config = AppConfig::default();
+        let fs_context = FileSystemContext::default();
+
+        let op = match backend.as_str() {
+            "fs" => {
+                let root = std::env::temp_dir().join("gvfs_mock_fs");
+                std::fs::create_dir_all(&root).expect("create fs backend root");
+                let mut fs_config = HashMap::new();
+                fs_config.insert("root".to_string(), root.to_string_lossy().to_string());
+                Operator::new(Fs::from_map(fs_config))
+                    .expect("opendal fs create failed")
+                    .layer(LoggingLayer::default())
+                    .finish()
+            }
+            _ => Operator::new(Memory::default())
+                .expect("opendal memory create failed")
+                .layer(LoggingLayer::default())
+                .finish(),
+        };
+
+        // Start each run from a clean test directory.
+        let file_name = cwd.to_string_lossy().to_string() + "/";
+        if op.exists(&file_name).await.unwrap_or(false) {
+            delete_dir(&op, &file_name).await;
+        }
+        op.create_dir(&file_name)
+            .await
+            .expect("create test dir failed");
+
+        let open_dal_fs = OpenDalFileSystem::new(op, &config, &fs_context, extract_multipart_config(&config));
+        S3FileSystem {
+            open_dal_fs,
+            bucket: String::new(),
+            region: S3FileSystem::DEFAULT_REGION.to_string(),
+            endpoint: None,
+            credentials: Arc::new(CredentialResolver {
+                static_keys: (None, None, None),
+                pinned: None,
+                cache: Mutex::new(None),
+            }),
+            auto_create_bucket: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_s3_file_system() {
+        let cwd = Path::new("/gvfs_mock1");
+        let fs = create_mock_s3_fs(cwd).await;
+
+        let _ = fs.init().await;
+        let mut tester = TestPathFileSystem::new(cwd, fs);
+        tester.test_path_file_system().await;
+    }
+
+    #[tokio::test]
+    async fn test_mock_s3_file_system_with_raw_file_system() {
+        let cwd = Path::new("/gvfs_mock2");
+        let s3_fs = create_mock_s3_fs(cwd).await;
+        let raw_fs =
+            DefaultRawFileSystem::new(s3_fs, &AppConfig::default(), &FileSystemContext::default());
+        let _ = raw_fs.init().await;
+        let mut tester = TestRawFileSystem::new(cwd, raw_fs);
+        tester.test_raw_file_system().await;
     }
 
     #[tokio::test]